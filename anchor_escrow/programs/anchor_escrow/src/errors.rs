@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Arithmetic operation overflowed")]
+    MathOverflow,
+    #[msg("Amount must be greater than zero")]
+    AmountZero,
+    #[msg("Vault does not hold enough balance for this operation")]
+    InsufficientVaultBalance,
+    #[msg("This offer has expired and can no longer be taken")]
+    EscrowExpired,
+    #[msg("This offer has not expired yet and cannot be refunded")]
+    EscrowNotYetExpired,
+    #[msg("remaining_accounts must hold exactly one full account group per fill")]
+    InvalidBatchLength,
+    #[msg("A remaining_accounts entry does not match the escrow it claims to belong to")]
+    AccountMismatch,
+    #[msg("Exchange rate index is out of bounds for this registrar")]
+    InvalidRateIndex,
+    #[msg("Exchange rate must be non-zero")]
+    RateZero,
+    #[msg("No whitelisted exchange rate exists at this index")]
+    RateNotFound,
+    #[msg("The escrow's mints do not match the whitelisted exchange rate")]
+    MintMismatch,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{EscrowError, ExchangeRateEntry, Registrar};
+
+#[derive(Accounts)]
+pub struct CreateExchangeRate<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+}
+
+impl<'info> CreateExchangeRate<'info> {
+    /// Whitelists (or replaces) the mint pair and rate at `idx`. `idx` must be
+    /// within bounds and `rate` must be non-zero.
+    pub fn create_exchange_rate(
+        &mut self,
+        idx: u8,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        rate: u64,
+        decimals: u8,
+    ) -> Result<()> {
+        require!(
+            (idx as usize) < crate::MAX_EXCHANGE_RATES,
+            EscrowError::InvalidRateIndex
+        );
+        require!(rate > 0, EscrowError::RateZero);
+
+        self.registrar.rates[idx as usize] = ExchangeRateEntry {
+            mint_a,
+            mint_b,
+            rate,
+            decimals,
+            in_use: true,
+        };
+
+        Ok(())
+    }
+}
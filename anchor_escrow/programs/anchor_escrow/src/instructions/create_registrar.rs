@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{ExchangeRateEntry, Registrar};
+
+#[derive(Accounts)]
+pub struct CreateRegistrar<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Registrar::INIT_SPACE,
+        seeds = [b"registrar", authority.key().as_ref()],
+        bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRegistrar<'info> {
+    pub fn create_registrar(&mut self, bumps: &CreateRegistrarBumps) -> Result<()> {
+        self.registrar.set_inner(Registrar {
+            authority: self.authority.key(),
+            bump: bumps.registrar,
+            rates: [ExchangeRateEntry::default(); crate::MAX_EXCHANGE_RATES],
+        });
+        Ok(())
+    }
+}
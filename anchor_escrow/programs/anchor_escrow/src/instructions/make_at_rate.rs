@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{Escrow, EscrowError, Registrar};
+
+/// Same shape as `Make`, but `receive` is derived from a whitelisted rate in
+/// `registrar` instead of being chosen freely by the maker.
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeAtRate<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        seeds = [b"registrar", registrar.authority.as_ref()],
+        bump = registrar.bump,
+    )]
+    pub registrar: Account<'info, Registrar>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeAtRate<'info> {
+    /// Looks up `idx` in the registrar, derives `receive = deposit * rate /
+    /// 10^decimals`, and initializes the escrow with it. Returns `receive` so
+    /// the caller can include it in the `EscrowMade` event.
+    pub fn init_escrow_at_rate(
+        &mut self,
+        seed: u64,
+        deposit: u64,
+        idx: u8,
+        expiry_ts: u64,
+        bumps: &MakeAtRateBumps,
+    ) -> Result<u64> {
+        require!(deposit > 0, EscrowError::AmountZero);
+        require!(
+            (idx as usize) < crate::MAX_EXCHANGE_RATES,
+            EscrowError::InvalidRateIndex
+        );
+
+        let entry = self.registrar.rates[idx as usize];
+        require!(entry.in_use, EscrowError::RateNotFound);
+        require_keys_eq!(entry.mint_a, self.mint_a.key(), EscrowError::MintMismatch);
+        require_keys_eq!(entry.mint_b, self.mint_b.key(), EscrowError::MintMismatch);
+
+        let receive = entry.receive_for_deposit(deposit)?;
+
+        self.escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            receive,
+            bump: bumps.escrow,
+            created_ts: Clock::get()?.unix_timestamp,
+            expiry_ts,
+            deposit_remaining: deposit,
+            receive_remaining: receive,
+        });
+
+        Ok(receive)
+    }
+
+    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)
+    }
+}
@@ -0,0 +1,20 @@
+pub mod make;
+pub use make::*;
+
+pub mod take;
+pub use take::*;
+
+pub mod refund;
+pub use refund::*;
+
+pub mod take_batch;
+pub use take_batch::*;
+
+pub mod create_registrar;
+pub use create_registrar::*;
+
+pub mod create_exchange_rate;
+pub use create_exchange_rate::*;
+
+pub mod make_at_rate;
+pub use make_at_rate::*;
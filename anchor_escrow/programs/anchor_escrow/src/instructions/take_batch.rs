@@ -0,0 +1,474 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{Escrow, EscrowError, EscrowTaken};
+
+/// Per-escrow fill request for `take_batch`, parallel to the account groups in
+/// `remaining_accounts`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BatchFill {
+    pub fill_amount: u64,
+}
+
+/// `remaining_accounts` carries one group of these per `BatchFill`, in order:
+/// `[escrow, vault, maker, mint_a, mint_b, maker_ata_b, taker_ata_a, taker_ata_b]`.
+const ACCOUNTS_PER_FILL: usize = 8;
+
+#[derive(Accounts)]
+pub struct TakeBatch<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Everything needed to execute one fill, once `check_fill` has confirmed the
+/// account group actually belongs to the claimed escrow and `fill_amount` is
+/// valid against it.
+struct PreparedFill<'a, 'info> {
+    escrow: Account<'info, Escrow>,
+    mint_a: InterfaceAccount<'info, Mint>,
+    mint_b: InterfaceAccount<'info, Mint>,
+    vault: InterfaceAccount<'info, TokenAccount>,
+    maker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    taker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    taker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    maker_info: &'a AccountInfo<'info>,
+    fill_amount: u64,
+    owed: u64,
+}
+
+/// Confirms every account in a batch-fill group is actually tied to the
+/// escrow it claims to belong to (the PDA itself, its maker, its mints, and
+/// the canonical ATAs for the vault/maker/taker) and that `fill_amount` is
+/// still fillable, then returns the amount owed to the maker. Kept free of
+/// `AccountInfo` so the key-matching logic can be unit tested directly.
+#[allow(clippy::too_many_arguments)]
+fn check_fill(
+    escrow: &Escrow,
+    escrow_key: Pubkey,
+    maker_key: Pubkey,
+    mint_a_key: Pubkey,
+    mint_b_key: Pubkey,
+    vault_key: Pubkey,
+    maker_ata_b_key: Pubkey,
+    taker_key: Pubkey,
+    taker_ata_a_key: Pubkey,
+    taker_ata_b_key: Pubkey,
+    token_program_id: Pubkey,
+    program_id: Pubkey,
+    fill_amount: u64,
+    now: i64,
+) -> Result<u64> {
+    let (expected_escrow, _) = Pubkey::find_program_address(
+        &[
+            b"escrow",
+            escrow.maker.as_ref(),
+            escrow.seed.to_le_bytes().as_ref(),
+        ],
+        &program_id,
+    );
+    require_keys_eq!(escrow_key, expected_escrow, EscrowError::AccountMismatch);
+    require_keys_eq!(escrow.maker, maker_key, EscrowError::AccountMismatch);
+    require_keys_eq!(escrow.mint_a, mint_a_key, EscrowError::AccountMismatch);
+    require_keys_eq!(escrow.mint_b, mint_b_key, EscrowError::AccountMismatch);
+
+    require_keys_eq!(
+        vault_key,
+        get_associated_token_address_with_program_id(
+            &expected_escrow,
+            &mint_a_key,
+            &token_program_id
+        ),
+        EscrowError::AccountMismatch
+    );
+    require_keys_eq!(
+        maker_ata_b_key,
+        get_associated_token_address_with_program_id(
+            &escrow.maker,
+            &mint_b_key,
+            &token_program_id
+        ),
+        EscrowError::AccountMismatch
+    );
+    require_keys_eq!(
+        taker_ata_a_key,
+        get_associated_token_address_with_program_id(&taker_key, &mint_a_key, &token_program_id),
+        EscrowError::AccountMismatch
+    );
+    require_keys_eq!(
+        taker_ata_b_key,
+        get_associated_token_address_with_program_id(&taker_key, &mint_b_key, &token_program_id),
+        EscrowError::AccountMismatch
+    );
+
+    require!(!escrow.has_expired(now), EscrowError::EscrowExpired);
+    require!(
+        fill_amount > 0 && fill_amount <= escrow.deposit_remaining,
+        EscrowError::InsufficientVaultBalance
+    );
+
+    escrow.owed_for_fill(fill_amount)
+}
+
+impl<'info> TakeBatch<'info> {
+    /// Fills as many of `fills` as it can. Each entry is validated *before* any
+    /// funds move, so an entry with a bad or mismatched account can simply be
+    /// skipped and logged. Once an entry passes validation its two transfers
+    /// are no longer optional: any failure past that point aborts the whole
+    /// batch transaction instead of being swallowed, so a taker can never end
+    /// up having paid the maker without receiving their fill (or vice versa).
+    /// Returns the number of entries filled.
+    pub fn take_batch(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        fills: Vec<BatchFill>,
+    ) -> Result<u64> {
+        require!(
+            remaining_accounts.len() == fills.len().saturating_mul(ACCOUNTS_PER_FILL),
+            EscrowError::InvalidBatchLength
+        );
+
+        let mut num_filled: u64 = 0;
+
+        for (i, fill) in fills.iter().enumerate() {
+            let group = &remaining_accounts[i * ACCOUNTS_PER_FILL..(i + 1) * ACCOUNTS_PER_FILL];
+
+            let prepared = match self.validate_entry(group, fill.fill_amount) {
+                Ok(prepared) => prepared,
+                Err(err) => {
+                    msg!("take_batch: entry {} skipped ({:?})", i, err);
+                    continue;
+                }
+            };
+
+            // Point of no return: `execute_entry` starts moving tokens, so from
+            // here a failure must propagate and abort the transaction.
+            let event = self.execute_entry(prepared)?;
+            num_filled = num_filled.saturating_add(1);
+            emit!(event);
+        }
+
+        Ok(num_filled)
+    }
+
+    fn validate_entry<'a>(
+        &self,
+        accounts: &'a [AccountInfo<'info>],
+        fill_amount: u64,
+    ) -> Result<PreparedFill<'a, 'info>> {
+        let [escrow_info, vault_info, maker_info, mint_a_info, mint_b_info, maker_ata_b_info, taker_ata_a_info, taker_ata_b_info] =
+            accounts
+        else {
+            return err!(EscrowError::InvalidBatchLength);
+        };
+
+        let escrow: Account<'info, Escrow> = Account::try_from(escrow_info)?;
+        let mint_a: InterfaceAccount<'info, Mint> = InterfaceAccount::try_from(mint_a_info)?;
+        let mint_b: InterfaceAccount<'info, Mint> = InterfaceAccount::try_from(mint_b_info)?;
+        let vault: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(vault_info)?;
+        let maker_ata_b: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(maker_ata_b_info)?;
+        let taker_ata_a: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(taker_ata_a_info)?;
+        let taker_ata_b: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(taker_ata_b_info)?;
+
+        let owed = check_fill(
+            &escrow,
+            *escrow_info.key,
+            *maker_info.key,
+            mint_a.key(),
+            mint_b.key(),
+            vault.key(),
+            maker_ata_b.key(),
+            self.taker.key(),
+            taker_ata_a.key(),
+            taker_ata_b.key(),
+            self.token_program.key(),
+            crate::ID,
+            fill_amount,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        Ok(PreparedFill {
+            escrow,
+            mint_a,
+            mint_b,
+            vault,
+            maker_ata_b,
+            taker_ata_a,
+            taker_ata_b,
+            maker_info,
+            fill_amount,
+            owed,
+        })
+    }
+
+    fn execute_entry(&self, mut prepared: PreparedFill<'_, 'info>) -> Result<EscrowTaken> {
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: prepared.taker_ata_b.to_account_info(),
+                    mint: prepared.mint_b.to_account_info(),
+                    to: prepared.maker_ata_b.to_account_info(),
+                    authority: self.taker.to_account_info(),
+                },
+            ),
+            prepared.owed,
+            prepared.mint_b.decimals,
+        )?;
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            prepared.escrow.maker.as_ref(),
+            &prepared.escrow.seed.to_le_bytes()[..],
+            &[prepared.escrow.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: prepared.vault.to_account_info(),
+                    mint: prepared.mint_a.to_account_info(),
+                    to: prepared.taker_ata_a.to_account_info(),
+                    authority: prepared.escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            prepared.fill_amount,
+            prepared.mint_a.decimals,
+        )?;
+
+        prepared.escrow.deposit_remaining = prepared
+            .escrow
+            .deposit_remaining
+            .checked_sub(prepared.fill_amount)
+            .ok_or(EscrowError::MathOverflow)?;
+        prepared.escrow.receive_remaining = prepared
+            .escrow
+            .receive_remaining
+            .checked_sub(prepared.owed)
+            .ok_or(EscrowError::MathOverflow)?;
+
+        let event = EscrowTaken {
+            escrow: prepared.escrow.key(),
+            seed: prepared.escrow.seed,
+            maker: prepared.escrow.maker,
+            taker: self.taker.key(),
+            mint_a: prepared.escrow.mint_a,
+            mint_b: prepared.escrow.mint_b,
+            fill_amount: prepared.fill_amount,
+            receive_amount: prepared.owed,
+        };
+
+        if prepared.escrow.deposit_remaining == 0 {
+            close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: prepared.vault.to_account_info(),
+                    destination: self.taker.to_account_info(),
+                    authority: prepared.escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ))?;
+            prepared.escrow.close(prepared.maker_info.clone())?;
+        } else {
+            prepared.escrow.exit(&crate::ID)?;
+        }
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow_for(maker: Pubkey, mint_a: Pubkey, mint_b: Pubkey, seed: u64) -> Escrow {
+        Escrow {
+            seed,
+            maker,
+            mint_a,
+            mint_b,
+            receive: 200,
+            bump: 0,
+            created_ts: 0,
+            expiry_ts: 0,
+            deposit_remaining: 100,
+            receive_remaining: 200,
+        }
+    }
+
+    #[test]
+    fn check_fill_accepts_a_correctly_derived_account_group() {
+        let program_id = Pubkey::new_unique();
+        let token_program_id = Pubkey::new_unique();
+        let maker = Pubkey::new_unique();
+        let taker = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let escrow = escrow_for(maker, mint_a, mint_b, 7);
+        let (escrow_key, _) = Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), escrow.seed.to_le_bytes().as_ref()],
+            &program_id,
+        );
+        let vault_key =
+            get_associated_token_address_with_program_id(&escrow_key, &mint_a, &token_program_id);
+        let maker_ata_b_key =
+            get_associated_token_address_with_program_id(&maker, &mint_b, &token_program_id);
+        let taker_ata_a_key =
+            get_associated_token_address_with_program_id(&taker, &mint_a, &token_program_id);
+        let taker_ata_b_key =
+            get_associated_token_address_with_program_id(&taker, &mint_b, &token_program_id);
+
+        let owed = check_fill(
+            &escrow,
+            escrow_key,
+            maker,
+            mint_a,
+            mint_b,
+            vault_key,
+            maker_ata_b_key,
+            taker,
+            taker_ata_a_key,
+            taker_ata_b_key,
+            token_program_id,
+            program_id,
+            40,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(owed, 80);
+    }
+
+    #[test]
+    fn check_fill_rejects_a_maker_ata_b_that_does_not_belong_to_the_maker() {
+        let program_id = Pubkey::new_unique();
+        let token_program_id = Pubkey::new_unique();
+        let maker = Pubkey::new_unique();
+        let taker = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let escrow = escrow_for(maker, mint_a, mint_b, 7);
+        let (escrow_key, _) = Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), escrow.seed.to_le_bytes().as_ref()],
+            &program_id,
+        );
+        let vault_key =
+            get_associated_token_address_with_program_id(&escrow_key, &mint_a, &token_program_id);
+        let taker_ata_a_key =
+            get_associated_token_address_with_program_id(&taker, &mint_a, &token_program_id);
+        let taker_ata_b_key =
+            get_associated_token_address_with_program_id(&taker, &mint_b, &token_program_id);
+        // A taker-controlled ATA substituted in place of the maker's.
+        let attacker_ata_b_key =
+            get_associated_token_address_with_program_id(&taker, &mint_b, &token_program_id);
+
+        let result = check_fill(
+            &escrow,
+            escrow_key,
+            maker,
+            mint_a,
+            mint_b,
+            vault_key,
+            attacker_ata_b_key,
+            taker,
+            taker_ata_a_key,
+            taker_ata_b_key,
+            token_program_id,
+            program_id,
+            40,
+            0,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn check_fill_judges_a_good_and_a_mismatched_entry_independently() {
+        let program_id = Pubkey::new_unique();
+        let token_program_id = Pubkey::new_unique();
+        let maker = Pubkey::new_unique();
+        let taker = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let good_escrow = escrow_for(maker, mint_a, mint_b, 1);
+        let (good_escrow_key, _) = Pubkey::find_program_address(
+            &[
+                b"escrow",
+                maker.as_ref(),
+                good_escrow.seed.to_le_bytes().as_ref(),
+            ],
+            &program_id,
+        );
+        let good_vault_key = get_associated_token_address_with_program_id(
+            &good_escrow_key,
+            &mint_a,
+            &token_program_id,
+        );
+        let maker_ata_b_key =
+            get_associated_token_address_with_program_id(&maker, &mint_b, &token_program_id);
+        let taker_ata_a_key =
+            get_associated_token_address_with_program_id(&taker, &mint_a, &token_program_id);
+        let taker_ata_b_key =
+            get_associated_token_address_with_program_id(&taker, &mint_b, &token_program_id);
+
+        // Good entry: every account derives to the escrow it claims.
+        let good = check_fill(
+            &good_escrow,
+            good_escrow_key,
+            maker,
+            mint_a,
+            mint_b,
+            good_vault_key,
+            maker_ata_b_key,
+            taker,
+            taker_ata_a_key,
+            taker_ata_b_key,
+            token_program_id,
+            program_id,
+            40,
+            0,
+        );
+
+        // Bad entry: vault key is an unrelated account, not the escrow's vault ATA.
+        let bad_escrow = escrow_for(maker, mint_a, mint_b, 2);
+        let (bad_escrow_key, _) = Pubkey::find_program_address(
+            &[
+                b"escrow",
+                maker.as_ref(),
+                bad_escrow.seed.to_le_bytes().as_ref(),
+            ],
+            &program_id,
+        );
+        let wrong_vault_key = Pubkey::new_unique();
+        let bad = check_fill(
+            &bad_escrow,
+            bad_escrow_key,
+            maker,
+            mint_a,
+            mint_b,
+            wrong_vault_key,
+            maker_ata_b_key,
+            taker,
+            taker_ata_a_key,
+            taker_ata_b_key,
+            token_program_id,
+            program_id,
+            40,
+            0,
+        );
+
+        assert!(good.is_ok());
+        assert!(bad.is_err());
+    }
+}
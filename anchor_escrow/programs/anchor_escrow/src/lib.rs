@@ -5,22 +5,116 @@ pub use instructions::*;
 pub mod state;
 pub use state::*;
 
+pub mod errors;
+pub use errors::*;
+
 declare_id!("6bcL1FE9Fg2rCri5jkyNmyeBcSTNmGro7nfpDx5SL5j3");
 
 #[program]
 pub mod anchor_escrow {
     use super::*;
 
-    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
-        ctx.accounts.init_escrow(seed, receive, &ctx.bumps)?;
-        ctx.accounts.deposit(deposit)
+    pub fn make(
+        ctx: Context<Make>,
+        seed: u64,
+        deposit: u64,
+        receive: u64,
+        expiry_ts: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .init_escrow(seed, deposit, receive, expiry_ts, &ctx.bumps)?;
+        ctx.accounts.deposit(deposit)?;
+
+        emit!(EscrowMade {
+            escrow: ctx.accounts.escrow.key(),
+            seed,
+            maker: ctx.accounts.maker.key(),
+            mint_a: ctx.accounts.mint_a.key(),
+            mint_b: ctx.accounts.mint_b.key(),
+            deposit,
+            receive,
+            expiry_ts,
+        });
+
+        Ok(())
     }
-    pub fn take(ctx: Context<Take>) -> Result<()> {
-        ctx.accounts.vault_transfer()?;
-        ctx.accounts.maker_transfer()?;
-        ctx.accounts.close_vault()
+    pub fn take(ctx: Context<Take>, fill_amount: u64) -> Result<()> {
+        ctx.accounts.vault_transfer(fill_amount)?;
+        let receive_amount = ctx.accounts.maker_transfer(fill_amount)?;
+        ctx.accounts.close_vault_if_filled()?;
+
+        emit!(EscrowTaken {
+            escrow: ctx.accounts.escrow.key(),
+            seed: ctx.accounts.escrow.seed,
+            maker: ctx.accounts.escrow.maker,
+            taker: ctx.accounts.taker.key(),
+            mint_a: ctx.accounts.escrow.mint_a,
+            mint_b: ctx.accounts.escrow.mint_b,
+            fill_amount,
+            receive_amount,
+        });
+
+        Ok(())
     }
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
-        ctx.accounts.process_refund()
+        let escrow = ctx.accounts.escrow.key();
+        let seed = ctx.accounts.escrow.seed;
+        let maker = ctx.accounts.maker.key();
+        let mint_a = ctx.accounts.mint_a.key();
+        let amount = ctx.accounts.vault.amount;
+
+        ctx.accounts.process_refund()?;
+
+        emit!(EscrowRefunded {
+            escrow,
+            seed,
+            maker,
+            mint_a,
+            amount,
+        });
+
+        Ok(())
+    }
+    pub fn take_batch(ctx: Context<TakeBatch>, fills: Vec<BatchFill>) -> Result<u64> {
+        ctx.accounts.take_batch(ctx.remaining_accounts, fills)
+    }
+    pub fn create_registrar(ctx: Context<CreateRegistrar>) -> Result<()> {
+        ctx.accounts.create_registrar(&ctx.bumps)
+    }
+    pub fn create_exchange_rate(
+        ctx: Context<CreateExchangeRate>,
+        idx: u8,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        rate: u64,
+        decimals: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_exchange_rate(idx, mint_a, mint_b, rate, decimals)
+    }
+    pub fn make_at_rate(
+        ctx: Context<MakeAtRate>,
+        seed: u64,
+        deposit: u64,
+        idx: u8,
+        expiry_ts: u64,
+    ) -> Result<()> {
+        let receive = ctx
+            .accounts
+            .init_escrow_at_rate(seed, deposit, idx, expiry_ts, &ctx.bumps)?;
+        ctx.accounts.deposit(deposit)?;
+
+        emit!(EscrowMade {
+            escrow: ctx.accounts.escrow.key(),
+            seed,
+            maker: ctx.accounts.maker.key(),
+            mint_a: ctx.accounts.mint_a.key(),
+            mint_b: ctx.accounts.mint_b.key(),
+            deposit,
+            receive,
+            expiry_ts,
+        });
+
+        Ok(())
     }
 }
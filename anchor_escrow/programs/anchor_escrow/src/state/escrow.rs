@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+use crate::EscrowError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+    /// Unix timestamp the offer was created at, set from the `Clock` sysvar in `init_escrow`.
+    pub created_ts: i64,
+    /// Unix timestamp after which the offer can no longer be taken and may be refunded.
+    /// `0` means the offer never expires, preserving the original no-deadline behavior.
+    pub expiry_ts: u64,
+    /// Amount of `mint_a` still sitting in the vault, available for further fills.
+    pub deposit_remaining: u64,
+    /// Amount of `mint_b` still owed to the maker across the remaining fills.
+    pub receive_remaining: u64,
+}
+
+impl Escrow {
+    /// `true` once `expiry_ts` has passed. An `expiry_ts` of `0` never expires.
+    pub fn has_expired(&self, now: i64) -> bool {
+        self.expiry_ts != 0 && now > self.expiry_ts as i64
+    }
+
+    /// `true` once the maker is allowed to reclaim the deposit: either the offer
+    /// never had a deadline, or that deadline has passed.
+    pub fn is_refundable(&self, now: i64) -> bool {
+        self.expiry_ts == 0 || self.has_expired(now)
+    }
+
+    /// Amount of `mint_b` owed to the maker for filling `fill_amount` of the
+    /// remaining `mint_a` deposit, scaled by the offer's remaining ratio. Uses a
+    /// `u128` intermediate to avoid overflow. Shared by `take` and `take_batch`
+    /// so both fill paths round the same way.
+    pub fn owed_for_fill(&self, fill_amount: u64) -> Result<u64> {
+        let owed = (fill_amount as u128)
+            .checked_mul(self.receive_remaining as u128)
+            .ok_or(EscrowError::MathOverflow)?
+            .checked_div(self.deposit_remaining as u128)
+            .ok_or(EscrowError::InsufficientVaultBalance)?;
+
+        require!(owed > 0, EscrowError::AmountZero);
+
+        Ok(owed as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow_with_remaining(deposit_remaining: u64, receive_remaining: u64) -> Escrow {
+        Escrow {
+            seed: 0,
+            maker: Pubkey::default(),
+            mint_a: Pubkey::default(),
+            mint_b: Pubkey::default(),
+            receive: receive_remaining,
+            bump: 0,
+            created_ts: 0,
+            expiry_ts: 0,
+            deposit_remaining,
+            receive_remaining,
+        }
+    }
+
+    #[test]
+    fn owed_for_fill_scales_by_remaining_ratio_without_exhausting_vault() {
+        let escrow = escrow_with_remaining(100, 200);
+
+        let owed = escrow.owed_for_fill(40).unwrap();
+
+        assert_eq!(owed, 80);
+    }
+
+    #[test]
+    fn owed_for_fill_covers_exact_exhaustion() {
+        let escrow = escrow_with_remaining(100, 200);
+
+        let owed = escrow.owed_for_fill(100).unwrap();
+
+        assert_eq!(owed, 200);
+    }
+
+    #[test]
+    fn owed_for_fill_rejects_a_fill_that_rounds_owed_to_zero() {
+        let escrow = escrow_with_remaining(100, 1);
+
+        let err = escrow.owed_for_fill(1).unwrap_err();
+
+        assert!(err.to_string().contains("Amount must be greater than zero"));
+    }
+}
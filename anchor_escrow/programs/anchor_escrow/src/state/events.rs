@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EscrowMade {
+    pub escrow: Pubkey,
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub deposit: u64,
+    pub receive: u64,
+    pub expiry_ts: u64,
+}
+
+#[event]
+pub struct EscrowTaken {
+    pub escrow: Pubkey,
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub fill_amount: u64,
+    pub receive_amount: u64,
+}
+
+#[event]
+pub struct EscrowRefunded {
+    pub escrow: Pubkey,
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub amount: u64,
+}
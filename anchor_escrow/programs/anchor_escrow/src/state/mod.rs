@@ -0,0 +1,8 @@
+pub mod escrow;
+pub use escrow::*;
+
+pub mod events;
+pub use events::*;
+
+pub mod registrar;
+pub use registrar::*;
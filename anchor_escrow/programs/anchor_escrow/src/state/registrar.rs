@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::EscrowError;
+
+/// Max number of whitelisted mint pairs a single `Registrar` can hold.
+pub const MAX_EXCHANGE_RATES: usize = 8;
+
+/// Singleton per-authority account whitelisting the mint pairs and fixed rates
+/// a venue operator allows `make_at_rate` offers to be created against.
+#[account]
+#[derive(InitSpace)]
+pub struct Registrar {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub rates: [ExchangeRateEntry; MAX_EXCHANGE_RATES],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default)]
+pub struct ExchangeRateEntry {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    /// `receive = deposit * rate / 10^decimals`.
+    pub rate: u64,
+    pub decimals: u8,
+    pub in_use: bool,
+}
+
+impl ExchangeRateEntry {
+    /// `receive` for a `deposit` of `mint_a` at this entry's fixed rate, i.e.
+    /// `deposit * rate / 10^decimals`. Uses a `u128` intermediate to avoid
+    /// overflow.
+    pub fn receive_for_deposit(&self, deposit: u64) -> Result<u64> {
+        let scale = 10u128
+            .checked_pow(self.decimals as u32)
+            .ok_or(EscrowError::MathOverflow)?;
+        let receive = (deposit as u128)
+            .checked_mul(self.rate as u128)
+            .ok_or(EscrowError::MathOverflow)?
+            .checked_div(scale)
+            .ok_or(EscrowError::MathOverflow)?;
+
+        require!(receive > 0, EscrowError::AmountZero);
+
+        u64::try_from(receive).map_err(|_| error!(EscrowError::MathOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(rate: u64, decimals: u8) -> ExchangeRateEntry {
+        ExchangeRateEntry {
+            mint_a: Pubkey::default(),
+            mint_b: Pubkey::default(),
+            rate,
+            decimals,
+            in_use: true,
+        }
+    }
+
+    #[test]
+    fn receive_for_deposit_scales_by_rate_and_decimals() {
+        // rate = 1.5 with 2 decimals (rate = 150), deposit of 1000 -> 1500.
+        let entry = entry(150, 2);
+
+        assert_eq!(entry.receive_for_deposit(1000).unwrap(), 1500);
+    }
+
+    #[test]
+    fn receive_for_deposit_rejects_a_deposit_that_rounds_receive_to_zero() {
+        let entry = entry(1, 6);
+
+        let err = entry.receive_for_deposit(1).unwrap_err();
+
+        assert!(err.to_string().contains("Amount must be greater than zero"));
+    }
+}